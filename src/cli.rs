@@ -0,0 +1,141 @@
+//! Command-line flags for one-shot and manual rotation, so the crate can be driven from a desktop
+//! launcher or a hotkey without leaving the daemon running (handy when the keyboard itself is off
+//! in tablet mode).
+
+use std::process::Command;
+
+use clap::Parser;
+
+use crate::LaptopOrientation;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+pub(crate) struct Cli {
+    /// Force a specific orientation, apply its side effects once and exit.
+    #[arg(long, value_enum, conflicts_with_all = ["next", "previous", "oneshot"])]
+    pub set: Option<SetOrientation>,
+
+    /// Cycle to the next orientation (normal -> left -> inverted -> right) and exit.
+    #[arg(long, conflicts_with_all = ["previous", "oneshot"])]
+    pub next: bool,
+
+    /// Cycle to the previous orientation (normal -> left -> inverted -> right) and exit.
+    #[arg(long, conflicts_with = "oneshot")]
+    pub previous: bool,
+
+    /// Read the accelerometer a single time, apply the side effects and exit, instead of running
+    /// the daemon loop.
+    #[arg(long)]
+    pub oneshot: bool,
+
+    /// The internal panel's output name (e.g. `eDP-1`), overriding both auto-detection and the
+    /// config file, so only that output gets rotated on multi-monitor setups.
+    #[arg(long)]
+    pub panel: Option<String>,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub(crate) enum SetOrientation {
+    Normal,
+    Left,
+    Right,
+    Inverted,
+    Tablet,
+    Tent,
+}
+
+impl From<SetOrientation> for LaptopOrientation {
+    fn from(value: SetOrientation) -> Self {
+        match value {
+            SetOrientation::Normal => LaptopOrientation::Normal,
+            SetOrientation::Left => LaptopOrientation::PortraitLeft,
+            SetOrientation::Right => LaptopOrientation::PortraitRight,
+            SetOrientation::Inverted | SetOrientation::Tent => LaptopOrientation::Tent,
+            SetOrientation::Tablet => LaptopOrientation::Tablet,
+        }
+    }
+}
+
+/// The four orientations reachable with `--next`/`--previous`, in cycling order, named as the
+/// matching `--set` values.
+const CYCLE: [&str; 4] = ["normal", "left", "inverted", "right"];
+
+/// Read the output of `xrandr --query` to figure out which orientation the display is currently
+/// in, so `--next`/`--previous` know where to advance from.
+///
+/// When `panel` is given, only that output's line is consulted, so `--next`/`--previous` cycle
+/// the internal panel's own orientation on multi-monitor setups instead of whichever connected
+/// output happens to come first.
+fn current_cycle_orientation(panel: Option<&str>) -> &'static str {
+    let output = Command::new("xrandr")
+        .arg("--query")
+        .output()
+        .expect("Couldn't run xrandr, is it properly installed?");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let current = stdout
+        .lines()
+        .filter(|line| line.contains(" connected"))
+        .find(|line| match panel {
+            Some(panel) => line.split_whitespace().next() == Some(panel),
+            None => true,
+        })
+        .and_then(|line| line.split('(').next())
+        .and_then(|before_parens| before_parens.split_whitespace().last())
+        .unwrap_or("normal");
+
+    // The backend rotates the screen `right` for `PortraitLeft` and `left` for `PortraitRight`,
+    // so xrandr's word for those two is the opposite of their name in `CYCLE`.
+    match current {
+        "left" => "right",
+        "right" => "left",
+        "inverted" => "inverted",
+        _ => "normal",
+    }
+}
+
+/// Compute the orientation reached by moving `step` positions (positive for `--next`, negative
+/// for `--previous`) around [`CYCLE`] from whatever `xrandr` currently reports for `panel` (or for
+/// the first connected output, if `panel` is `None`).
+pub(crate) fn cycle(step: isize, panel: Option<&str>) -> LaptopOrientation {
+    orientation_after(current_cycle_orientation(panel), step)
+}
+
+/// The pure wraparound arithmetic behind [`cycle`], split out so it's testable without shelling
+/// out to `xrandr`.
+fn orientation_after(current: &str, step: isize) -> LaptopOrientation {
+    let index = CYCLE.iter().position(|&o| o == current).unwrap_or(0).cast_signed();
+    let len = CYCLE.len().cast_signed();
+    let next = CYCLE[((index + step).rem_euclid(len)) as usize];
+
+    match next {
+        "left" => LaptopOrientation::PortraitLeft,
+        "right" => LaptopOrientation::PortraitRight,
+        "inverted" => LaptopOrientation::Tent,
+        _ => LaptopOrientation::Normal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::orientation_after;
+    use crate::LaptopOrientation;
+
+    #[test]
+    fn steps_forward_through_the_cycle() {
+        assert_eq!(orientation_after("normal", 1), LaptopOrientation::PortraitLeft);
+        assert_eq!(orientation_after("left", 1), LaptopOrientation::Tent);
+        assert_eq!(orientation_after("inverted", 1), LaptopOrientation::PortraitRight);
+    }
+
+    #[test]
+    fn wraps_forward_past_the_end() {
+        assert_eq!(orientation_after("right", 1), LaptopOrientation::Normal);
+    }
+
+    #[test]
+    fn wraps_backward_past_the_start() {
+        assert_eq!(orientation_after("normal", -1), LaptopOrientation::PortraitRight);
+    }
+}
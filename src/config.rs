@@ -0,0 +1,158 @@
+//! User-overridable configuration, loaded from `~/.config/tablet-pc-rotation/config.toml`.
+//!
+//! Device names and accelerometer thresholds vary enough across laptops (the Lenovo MIIX and
+//! Surface lines hit exactly these name/threshold mismatches) that hardcoding them doesn't scale;
+//! this lets a user point the crate at their own hardware without recompiling. Falls back to the
+//! built-in defaults when no file is present.
+
+use std::fs::read_to_string;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+/// Substrings used to find each kind of input device in the backend's device list.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub(crate) struct Devices {
+    pub keyboard: Vec<String>,
+    pub touchpad: Vec<String>,
+    pub touchscreen: Vec<String>,
+}
+
+impl Default for Devices {
+    fn default() -> Self {
+        Devices {
+            keyboard: vec![String::from("AT Translated Set 2 keyboard")],
+            touchpad: vec![String::from("Touchpad"), String::from("Trackpoint")],
+            touchscreen: vec![String::from("touchscreen"), String::from("wacom")],
+        }
+    }
+}
+
+/// Accelerometer value ranges, in the same normalized units as [`crate::Accelerometer`], used to
+/// tell orientations apart. See [`crate::Accelerometer::which_orientation`].
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub(crate) struct Thresholds {
+    pub portrait_left: (f64, f64),
+    pub portrait_right: (f64, f64),
+    pub tablet: (f64, f64),
+    pub tent: (f64, f64),
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Thresholds {
+            portrait_left: (-11.0, -5.0),
+            portrait_right: (5.0, 11.0),
+            tablet: (-11.0, -7.0),
+            tent: (7.0, 11.0),
+        }
+    }
+}
+
+impl Thresholds {
+    pub fn portrait_left(&self) -> RangeInclusive<f64> {
+        self.portrait_left.0..=self.portrait_left.1
+    }
+
+    pub fn portrait_right(&self) -> RangeInclusive<f64> {
+        self.portrait_right.0..=self.portrait_right.1
+    }
+
+    pub fn tablet(&self) -> RangeInclusive<f64> {
+        self.tablet.0..=self.tablet.1
+    }
+
+    pub fn tent(&self) -> RangeInclusive<f64> {
+        self.tent.0..=self.tent.1
+    }
+}
+
+/// Output-related overrides, for picking the internal panel apart from external monitors.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub(crate) struct Output {
+    /// The internal panel's output name (e.g. `eDP-1`), for when auto-detection (anything named
+    /// `eDP*`/`LVDS*`) picks the wrong one or doesn't apply to the backend.
+    pub panel: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub(crate) struct Config {
+    pub devices: Devices,
+    pub thresholds: Thresholds,
+    pub output: Output,
+    pub poll_interval_secs: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            devices: Devices::default(),
+            thresholds: Thresholds::default(),
+            output: Output::default(),
+            poll_interval_secs: 2,
+        }
+    }
+}
+
+impl Config {
+    /// Load `~/.config/tablet-pc-rotation/config.toml`, falling back to the built-in defaults
+    /// when the file doesn't exist or fails to parse.
+    pub fn load() -> Config {
+        Self::path()
+            .and_then(|path| read_to_string(path).ok())
+            .and_then(|raw| toml::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    fn path() -> Option<PathBuf> {
+        std::env::var_os("HOME").map(|home| {
+            PathBuf::from(home)
+                .join(".config")
+                .join("tablet-pc-rotation")
+                .join("config.toml")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, Devices, Thresholds};
+
+    #[test]
+    fn empty_toml_falls_back_to_every_default() {
+        let config: Config = toml::from_str("").unwrap();
+
+        assert_eq!(config.devices.keyboard, Devices::default().keyboard);
+        assert_eq!(config.thresholds.tablet, Thresholds::default().tablet);
+        assert_eq!(config.output.panel, None);
+        assert_eq!(config.poll_interval_secs, 2);
+    }
+
+    #[test]
+    fn partial_toml_only_overrides_what_it_sets() {
+        let config: Config = toml::from_str("poll_interval_secs = 5").unwrap();
+
+        assert_eq!(config.poll_interval_secs, 5);
+        assert_eq!(config.devices.keyboard, Devices::default().keyboard);
+        assert_eq!(config.thresholds.tablet, Thresholds::default().tablet);
+    }
+
+    #[test]
+    fn partial_section_only_overrides_the_field_it_sets() {
+        let config: Config = toml::from_str(
+            r"
+            [thresholds]
+            tablet = [-12.0, -8.0]
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(config.thresholds.tablet, (-12.0, -8.0));
+        assert_eq!(config.thresholds.tent, Thresholds::default().tent);
+    }
+}
@@ -0,0 +1,63 @@
+//! Abstraction over the windowing system so the rotation logic doesn't have to care whether it's
+//! running under X11 or a Wayland compositor.
+
+use std::env;
+use std::io;
+
+use crate::LaptopOrientation;
+
+pub(crate) mod sway;
+pub(crate) mod x11;
+
+/// The rotation matrix applied to a touch/stylus input so its coordinates keep matching the
+/// rotated screen, as a row-major 3x3 matrix (the same convention as X11's
+/// "Coordinate Transformation Matrix").
+pub(crate) type InputMatrix = [i32; 9];
+
+/// Side effects needed to rotate the display and its associated inputs, implemented once per
+/// windowing system.
+pub(crate) trait DisplayBackend {
+    /// List the names/identifiers of the available input devices.
+    fn list_inputs(&self) -> io::Result<Vec<String>>;
+
+    /// Rotate the screen output to match `orientation`.
+    fn rotate_output(&self, orientation: LaptopOrientation) -> io::Result<()>;
+
+    /// Enable or disable an input device by name/identifier.
+    fn toggle_input(&self, input: &str, enable: bool) -> io::Result<()>;
+
+    /// Apply the touch input transformation matrix to an input device by name/identifier.
+    fn set_input_matrix(&self, input: &str, matrix: &InputMatrix) -> io::Result<()>;
+
+    /// Devices that expose a calibration/transformation matrix and should therefore be treated as
+    /// rotatable touch inputs, regardless of whether their name matches the configured
+    /// touchscreen substrings. Backends that have no cheap way to introspect this return an empty
+    /// list, leaving name-based matching as the only source.
+    fn calibratable_inputs(&self) -> io::Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+}
+
+/// Pick the right [`DisplayBackend`] for the current session, based on `$WAYLAND_DISPLAY` and
+/// `$XDG_SESSION_TYPE`. `panel` overrides which output is treated as the internal panel on
+/// multi-monitor setups; `None` means auto-detect it.
+pub(crate) fn detect_backend(panel: Option<String>) -> Box<dyn DisplayBackend> {
+    let is_wayland = env::var("WAYLAND_DISPLAY").is_ok()
+        || env::var("XDG_SESSION_TYPE").is_ok_and(|value| value == "wayland");
+
+    if is_wayland {
+        Box::new(sway::SwayBackend::new(panel))
+    } else {
+        Box::new(x11::X11Backend::new(panel))
+    }
+}
+
+/// Compute the transformation matrix to apply to touch inputs for a given orientation.
+pub(crate) fn input_matrix_for(orientation: LaptopOrientation) -> InputMatrix {
+    match orientation {
+        LaptopOrientation::Normal | LaptopOrientation::Tablet => [1, 0, 0, 0, 1, 0, 0, 0, 1],
+        LaptopOrientation::PortraitLeft => [0, 1, 0, -1, 0, 1, 0, 0, 1],
+        LaptopOrientation::PortraitRight => [0, -1, 1, 1, 0, 0, 0, 0, 1],
+        LaptopOrientation::Tent => [-1, 0, 1, 0, -1, 1, 0, 0, 1],
+    }
+}
@@ -0,0 +1,161 @@
+//! Sway/wlroots backend, driving `swaymsg` for output rotation and input handling, for users on a
+//! Wayland compositor where `xrandr`/`xinput` aren't available.
+
+use std::io;
+use std::process::Command;
+
+use super::{DisplayBackend, InputMatrix};
+use crate::LaptopOrientation;
+
+pub(crate) struct SwayBackend {
+    /// The internal panel's output name, as configured by the user; `None` means auto-detect it
+    /// from `swaymsg -t get_outputs -r` every time it's needed.
+    panel_override: Option<String>,
+}
+
+impl SwayBackend {
+    pub(crate) fn new(panel_override: Option<String>) -> Self {
+        SwayBackend { panel_override }
+    }
+
+    /// Resolve the output to operate on: the configured override if there is one, otherwise
+    /// whichever output looks like the internal panel (`eDP*`/`LVDS*`), mirroring the X11
+    /// backend's `panel()`, so plugging in an external monitor doesn't silently widen
+    /// `rotate_output`'s effect to every connected output. Falls back to Sway's `*` wildcard only
+    /// if no output name can be found at all.
+    fn output(&self) -> io::Result<String> {
+        if let Some(panel) = &self.panel_override {
+            return Ok(panel.clone());
+        }
+
+        let output = Command::new("swaymsg")
+            .args(["-t", "get_outputs", "-r"])
+            .output()
+            .expect("Couldn't run swaymsg, is sway running?");
+
+        assert!(output.status.success(), "swaymsg failed to list the outputs.");
+
+        let output = String::from_utf8(output.stdout).map_err(io::Error::other)?;
+
+        // No JSON dependency in this crate yet, so we pick the "name" fields out of the raw JSON
+        // by hand rather than pull one in just for this (same approach as `list_inputs` below).
+        let names: Vec<&str> = output
+            .split("\"name\":")
+            .skip(1)
+            .filter_map(|chunk| {
+                let start = chunk.find('"')? + 1;
+                let end = chunk[start..].find('"')? + start;
+                Some(&chunk[start..end])
+            })
+            .collect();
+
+        let panel = names
+            .iter()
+            .find(|name| name.starts_with("eDP") || name.starts_with("LVDS"))
+            .or_else(|| names.first())
+            .unwrap_or(&"*");
+
+        Ok((*panel).to_string())
+    }
+}
+
+/// Run a `swaymsg` command and fail loudly if sway rejected it.
+fn run_swaymsg(command: &str, err_msg: &str) -> io::Result<()> {
+    let status = Command::new("swaymsg")
+        .arg(command)
+        .status()
+        .expect("Couldn't run `swaymsg`, is sway running?");
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(err_msg))
+    }
+}
+
+/// Restrict a touch input's active area to a single output, so it stays aligned with the rotated
+/// internal panel instead of spanning every connected output.
+fn map_to_output(input: &str, output: &str) -> io::Result<()> {
+    run_swaymsg(
+        &format!("input {input} map_to_output {output}"),
+        &format!("swaymsg couldn't map '{input}' to output '{output}'"),
+    )
+}
+
+impl DisplayBackend for SwayBackend {
+    /// List input identifiers known to sway, via `swaymsg -t get_inputs -r`.
+    fn list_inputs(&self) -> io::Result<Vec<String>> {
+        let output = Command::new("swaymsg")
+            .args(["-t", "get_inputs", "-r"])
+            .output()
+            .expect("Failed to run swaymsg, is sway running?");
+
+        assert!(output.status.success(), "swaymsg failed to list the inputs.");
+
+        let output = String::from_utf8(output.stdout).map_err(io::Error::other)?;
+
+        // No JSON dependency in this crate yet, so we pick the "identifier" fields out of the
+        // raw JSON by hand rather than pull one in just for this.
+        let identifiers = output
+            .split("\"identifier\":")
+            .skip(1)
+            .filter_map(|chunk| {
+                let start = chunk.find('"')? + 1;
+                let end = chunk[start..].find('"')? + start;
+                Some(chunk[start..end].to_string())
+            })
+            .collect();
+
+        Ok(identifiers)
+    }
+
+    /// Using `swaymsg output ... transform ...`, rotate the output based on the laptop
+    /// orientation.
+    ///
+    /// `wl_output` transforms are counter-clockwise, the opposite sense of the X11 backend's
+    /// `xrandr --rotate left/right`, so the two are swapped relative to their xrandr counterparts.
+    fn rotate_output(&self, orientation: LaptopOrientation) -> io::Result<()> {
+        let transform = match orientation {
+            LaptopOrientation::Normal | LaptopOrientation::Tablet => "normal",
+            LaptopOrientation::PortraitLeft => "270",
+            LaptopOrientation::PortraitRight => "90",
+            LaptopOrientation::Tent => "180",
+        };
+
+        run_swaymsg(
+            &format!("output {} transform {}", self.output()?, transform),
+            "swaymsg couldn't rotate the output",
+        )
+    }
+
+    /// Using `swaymsg input ... events enabled/disabled`, enable or disable an input device.
+    fn toggle_input(&self, input: &str, enable: bool) -> io::Result<()> {
+        let action = if enable { "enabled" } else { "disabled" };
+
+        run_swaymsg(
+            &format!("input {input} events {action}"),
+            &format!("swaymsg couldn't set events {action} on {input}"),
+        )
+    }
+
+    /// Using `swaymsg input ... calibration_matrix ...`, apply the touch rotation matrix, then
+    /// scope the input to the internal panel's output, mirroring the X11 backend's
+    /// `map_to_output` call.
+    ///
+    /// libinput's calibration matrix is a 2x3 affine transform (the 3rd row of our 3x3
+    /// convention is always `0 0 1`), so only the first two rows are sent.
+    fn set_input_matrix(&self, input: &str, matrix: &InputMatrix) -> io::Result<()> {
+        let values = matrix[0..6]
+            .iter()
+            .map(std::string::ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        run_swaymsg(
+            &format!("input {input} calibration_matrix \"{values}\""),
+            &format!("swaymsg couldn't set the calibration matrix on {input}"),
+        )?;
+
+        map_to_output(input, &self.output()?)
+    }
+}
@@ -0,0 +1,212 @@
+//! X11 backend, driving `xrandr` for output rotation and `xinput` for the inputs. This is the
+//! original backend this crate shipped with.
+
+use std::io::{self, ErrorKind};
+use std::process::Command;
+
+use super::{DisplayBackend, InputMatrix};
+use crate::LaptopOrientation;
+
+pub(crate) struct X11Backend {
+    /// The internal panel's output name, as configured by the user; `None` means auto-detect it
+    /// from `xrandr --query` every time it's needed.
+    panel_override: Option<String>,
+}
+
+impl X11Backend {
+    pub(crate) fn new(panel_override: Option<String>) -> Self {
+        X11Backend { panel_override }
+    }
+
+    /// Resolve the output to rotate: the configured override if there is one, otherwise whichever
+    /// connected output looks like the internal panel (`eDP*`/`LVDS*`), falling back to the first
+    /// connected output so a plain desktop still works.
+    fn panel(&self) -> io::Result<String> {
+        if let Some(panel) = &self.panel_override {
+            return Ok(panel.clone());
+        }
+
+        let output = Command::new("xrandr")
+            .arg("--query")
+            .output()
+            .expect("Couldn't run xrandr, is it properly installed?");
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let connected: Vec<&str> = stdout
+            .lines()
+            .filter(|line| line.contains(" connected"))
+            .filter_map(|line| line.split_whitespace().next())
+            .collect();
+
+        connected
+            .iter()
+            .find(|name| name.starts_with("eDP") || name.starts_with("LVDS"))
+            .or_else(|| connected.first())
+            .map(std::string::ToString::to_string)
+            .ok_or_else(|| io::Error::new(ErrorKind::NotFound, "no connected xrandr output found"))
+    }
+}
+
+/// Helper function to reduce duplication of code when calling xrandr to rotate a single output,
+/// leaving any other connected monitor untouched.
+fn call_xrandr(output: &str, rotation: &str, err_msg: &str) -> io::Result<()> {
+    let status = Command::new("xrandr")
+        .args(["--output", output, "--rotate", rotation])
+        .status()
+        .expect("Couldn't run xrandr, is it properly installed?");
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(err_msg))
+    }
+}
+
+/// Restrict a touch input's active area to a single output, so it stays aligned with the rotated
+/// internal panel instead of spanning the whole virtual screen.
+fn map_to_output(input: &str, output: &str) -> io::Result<()> {
+    let status = Command::new("xinput")
+        .args(["map-to-output", input, output])
+        .status()
+        .expect("Couldn't run `xinput`, are you sure it's installed properly?");
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "xinput couldn't map '{input}' to output '{output}'"
+        )))
+    }
+}
+
+/// The two property names X drivers use to expose a touch rotation matrix: `xf86-input-libinput`
+/// calls it "libinput Calibration Matrix", while the older evdev/wacom drivers (and `xrandr`'s own
+/// convention) call it "Coordinate Transformation Matrix".
+const MATRIX_PROPERTIES: [&str; 2] = ["libinput Calibration Matrix", "Coordinate Transformation Matrix"];
+
+/// List the `xinput list-props` output for a device, to check which properties it exposes.
+fn list_props(input: &str) -> io::Result<String> {
+    let output = Command::new("xinput")
+        .args(["list-props", input])
+        .output()
+        .expect("Couldn't run `xinput`, are you sure it's installed properly?");
+
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "xinput couldn't list the properties of '{input}'"
+        )));
+    }
+
+    String::from_utf8(output.stdout).map_err(io::Error::other)
+}
+
+/// Whichever of [`MATRIX_PROPERTIES`] this device actually advertises, if any.
+fn matrix_property(input: &str) -> io::Result<Option<&'static str>> {
+    let props = list_props(input)?;
+
+    Ok(MATRIX_PROPERTIES
+        .iter()
+        .copied()
+        .find(|property| props.contains(property)))
+}
+
+impl DisplayBackend for X11Backend {
+    /// Using xinput, list the available inputs.
+    fn list_inputs(&self) -> io::Result<Vec<String>> {
+        let output = Command::new("xinput")
+            .args(["list", "--name-only"])
+            .output()
+            .expect("Failed to run xinput, is it properly installed?");
+
+        assert!(output.status.success(), "xinput failed to list the inputs.");
+
+        let output = String::from_utf8(output.stdout).map_err(io::Error::other)?;
+
+        let inputs: Vec<String> = output
+            .lines()
+            .map(std::string::ToString::to_string)
+            .collect();
+
+        Ok(inputs)
+    }
+
+    /// Using xrandr, rotate only the internal panel output based on the laptop orientation,
+    /// leaving any external monitor untouched.
+    fn rotate_output(&self, orientation: LaptopOrientation) -> io::Result<()> {
+        let panel = self.panel()?;
+
+        match orientation {
+            LaptopOrientation::Normal | LaptopOrientation::Tablet => call_xrandr(
+                &panel,
+                "normal",
+                "xrandr couldn't rotate screen in normal orientation",
+            ),
+            LaptopOrientation::PortraitLeft => {
+                call_xrandr(&panel, "right", "xrandr couldn't rotate screen right")
+            }
+            LaptopOrientation::PortraitRight => {
+                call_xrandr(&panel, "left", "xrandr couldn't rotate screen to the left")
+            }
+            LaptopOrientation::Tent => {
+                call_xrandr(&panel, "inverted", "xrandr couldn't rotate screen 180\u{b0}")
+            }
+        }
+    }
+
+    /// Using `xinput`, enable or disable an input device.
+    fn toggle_input(&self, input: &str, enable: bool) -> io::Result<()> {
+        let action = if enable { "enable" } else { "disable" };
+        let status = Command::new("xinput")
+            .arg(action)
+            .arg(input)
+            .status()
+            .expect("Couldn't run `xinput`, are you sure it's installed properly?");
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(io::Error::other(format!("xinput couldn't {action} {input}")))
+        }
+    }
+
+    /// Using `xinput`, set whichever of [`MATRIX_PROPERTIES`] this touch input actually exposes,
+    /// then scope it to the internal panel's geometry so it stays aligned with that output alone.
+    fn set_input_matrix(&self, input: &str, matrix: &InputMatrix) -> io::Result<()> {
+        let property = matrix_property(input)?.ok_or_else(|| {
+            io::Error::new(
+                ErrorKind::NotFound,
+                format!("'{input}' exposes no calibration matrix property"),
+            )
+        })?;
+
+        let mut xinput = Command::new("xinput");
+        let command = xinput.arg("set-prop").arg(input).arg(property);
+
+        for number in matrix {
+            command.arg(number.to_string());
+        }
+
+        let status = command
+            .status()
+            .expect("Couldn't run `xinput`, are you sure it's installed properly?");
+
+        if !status.success() {
+            return Err(io::Error::other(format!("xinput couldn't rotate '{input}'")));
+        }
+
+        map_to_output(input, &self.panel()?)
+    }
+
+    /// Any device advertising either of [`MATRIX_PROPERTIES`] is a rotatable touch input, whether
+    /// or not its name matches the configured touchscreen substrings.
+    fn calibratable_inputs(&self) -> io::Result<Vec<String>> {
+        self.list_inputs()?
+            .into_iter()
+            .filter_map(|input| match matrix_property(&input) {
+                Ok(Some(_)) => Some(Ok(input)),
+                Ok(None) => None,
+                Err(err) => Some(Err(err)),
+            })
+            .collect()
+    }
+}
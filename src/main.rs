@@ -19,14 +19,22 @@
 #![deny(clippy::all)]
 #![deny(clippy::pedantic)]
 
-use std::fs::read_to_string;
-use std::io::{self, Error, ErrorKind};
+use std::fs::{read_dir, read_to_string};
+use std::io::{self, ErrorKind};
 use std::num::ParseIntError;
-use std::path::Path;
-use std::process::Command;
+use std::path::{Path, PathBuf};
 use std::thread::sleep;
 use std::time::Duration;
 
+mod cli;
+mod config;
+mod display_backend;
+mod sensor_proxy;
+
+use clap::Parser;
+use config::{Config, Thresholds};
+use display_backend::DisplayBackend;
+
 // --------------------------------------
 //
 // Gather the inputs of the program
@@ -39,6 +47,15 @@ enum ReadError {
     ParseError(ParseIntError),
 }
 
+impl std::fmt::Display for ReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadError::IOError(err) => write!(f, "{err}"),
+            ReadError::ParseError(err) => write!(f, "{err}"),
+        }
+    }
+}
+
 impl From<io::Error> for ReadError {
     fn from(error: io::Error) -> Self {
         ReadError::IOError(error)
@@ -54,7 +71,7 @@ impl From<ParseIntError> for ReadError {
 /// Read the file and return its content, which is supposed to be a single value in a single line.
 fn read_value(path: &Path) -> Result<f64, ReadError> {
     let raw = read_to_string(path)
-        .map_err(|_| io::Error::new(ErrorKind::NotFound, format!("file {:?} not found", path)))?;
+        .map_err(|_| io::Error::new(ErrorKind::NotFound, format!("file {} not found", path.display())))?;
 
     // TODO: simplify the control flow with `or_else` chaining. Didn't manage yet.
     if let Ok(value) = raw.trim().parse::<f64>() {
@@ -66,26 +83,144 @@ fn read_value(path: &Path) -> Result<f64, ReadError> {
     }
 }
 
-/// Using xinput, list the available inputs.
-fn list_input_devices() -> io::Result<Vec<String>> {
-    let output = Command::new("xinput")
-        .args(&["list", "--name-only"])
-        .output()
-        .expect("Failed to run xinput, is it properly installed?");
+/// The five accelerometer sysfs files we need, rooted at whichever `iio:deviceN` directory turned
+/// out to hold the accelerometer.
+struct AccelPaths {
+    x: PathBuf,
+    y: PathBuf,
+    z: PathBuf,
+    scale: PathBuf,
+    offset: PathBuf,
+}
+
+/// Scan `/sys/bus/iio/devices/iio:device*` for the device exposing `in_accel_{x,y,z}_raw`, since
+/// the device index isn't stable across machines (it isn't always `iio:device0`).
+/// When several devices qualify, the one whose `name` file contains "accel" wins.
+fn discover_accelerometer() -> io::Result<AccelPaths> {
+    discover_accelerometer_in(Path::new("/sys/bus/iio/devices"))
+}
+
+/// The scan/select logic behind [`discover_accelerometer`], parameterized over the IIO devices
+/// root so it's testable against a fake directory tree instead of the real sysfs.
+fn discover_accelerometer_in(root: &Path) -> io::Result<AccelPaths> {
+    let mut candidates: Vec<PathBuf> = read_dir(root)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("iio:device"))
+        })
+        .collect();
+
+    candidates.sort_by_key(|path| {
+        let name = read_to_string(path.join("name")).unwrap_or_default();
+        !name.to_ascii_lowercase().contains("accel")
+    });
+
+    candidates
+        .into_iter()
+        .find(|path| {
+            ["in_accel_x_raw", "in_accel_y_raw", "in_accel_z_raw"]
+                .iter()
+                .all(|file| path.join(file).is_file())
+        })
+        .map(|path| AccelPaths {
+            x: path.join("in_accel_x_raw"),
+            y: path.join("in_accel_y_raw"),
+            z: path.join("in_accel_z_raw"),
+            scale: path.join("in_accel_scale"),
+            offset: path.join("in_accel_offset"),
+        })
+        .ok_or_else(|| {
+            io::Error::new(
+                ErrorKind::NotFound,
+                "no accelerometer found under /sys/bus/iio/devices",
+            )
+        })
+}
+
+#[cfg(test)]
+mod discover_accelerometer_tests {
+    use std::fs::{create_dir_all, remove_dir_all, write};
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::discover_accelerometer_in;
+
+    /// A scratch directory tree under `std::env::temp_dir()`, removed on drop, standing in for
+    /// `/sys/bus/iio/devices` since there's no `tempfile` dependency in this crate.
+    struct FakeIioRoot(PathBuf);
+
+    impl FakeIioRoot {
+        fn new() -> Self {
+            static COUNTER: AtomicU32 = AtomicU32::new(0);
+            let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+            let path = std::env::temp_dir().join(format!(
+                "tablet-pc-rotation-test-{}-{n}",
+                std::process::id()
+            ));
+            create_dir_all(&path).unwrap();
+            FakeIioRoot(path)
+        }
+
+        fn device(&self, device: &str, name: Option<&str>, has_accel_files: bool) -> &Self {
+            let dir = self.0.join(device);
+            create_dir_all(&dir).unwrap();
+
+            if let Some(name) = name {
+                write(dir.join("name"), name).unwrap();
+            }
+
+            if has_accel_files {
+                for file in ["in_accel_x_raw", "in_accel_y_raw", "in_accel_z_raw"] {
+                    write(dir.join(file), "0").unwrap();
+                }
+            }
+
+            self
+        }
 
-    if !output.status.success() {
-        panic!("xinput failed to list the inputs.");
+        fn path(&self) -> &Path {
+            &self.0
+        }
+    }
+
+    impl Drop for FakeIioRoot {
+        fn drop(&mut self) {
+            let _ = remove_dir_all(&self.0);
+        }
     }
 
-    let output =
-        String::from_utf8(output.stdout).map_err(|err| Error::new(ErrorKind::Other, err))?;
+    #[test]
+    fn picks_the_only_device_exposing_accel_files() {
+        let root = FakeIioRoot::new();
+        root.device("iio:device0", Some("some-light-sensor"), false)
+            .device("iio:device1", Some("cros-ec-accel"), true);
 
-    let inputs: Vec<String> = output
-        .lines()
-        .map(std::string::ToString::to_string)
-        .collect();
+        let paths = discover_accelerometer_in(root.path()).unwrap();
+
+        assert_eq!(paths.x, root.path().join("iio:device1/in_accel_x_raw"));
+    }
+
+    #[test]
+    fn prefers_the_device_whose_name_mentions_accel_when_several_qualify() {
+        let root = FakeIioRoot::new();
+        root.device("iio:device0", Some("kxcjk1013"), true)
+            .device("iio:device1", Some("cros-ec-accel"), true);
+
+        let paths = discover_accelerometer_in(root.path()).unwrap();
+
+        assert_eq!(paths.x, root.path().join("iio:device1/in_accel_x_raw"));
+    }
+
+    #[test]
+    fn errors_when_no_device_exposes_accel_files() {
+        let root = FakeIioRoot::new();
+        root.device("iio:device0", Some("some-light-sensor"), false);
 
-    Ok(inputs)
+        assert!(discover_accelerometer_in(root.path()).is_err());
+    }
 }
 
 // --------------------------------------
@@ -97,7 +232,8 @@ fn list_input_devices() -> io::Result<Vec<String>> {
 /// Representation of the various physical modes of using the laptop. The orientation described are
 /// those that makes the most sense and assume that unless in normal mode the keyboard is not meant
 /// be used and retracted behind the screen.
-enum LaptopOrientation {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LaptopOrientation {
     /// "normal mode", the laptop is opened, keyboard horizontal and screen vertical
     Normal,
     /// From normal mode, rotate the laptop to the left
@@ -185,17 +321,18 @@ impl Accelerometer {
     /// They're voluntarily a bit large to allow for detecting the next orientation before the user
     /// actually finished rotating the device with some margin of error (nobody will have a laptop
     /// perfectly vertical for instance), so that hopefully when he's done the intended orientation
-    /// has already been detected.
-    pub fn which_orientation(&self) -> LaptopOrientation {
-        if (-11.0..=-5.0).contains(&self.x) {
+    /// has already been detected. `thresholds` lets a user override these ranges for their own
+    /// hardware; see [`config::Thresholds`].
+    pub fn which_orientation(&self, thresholds: &Thresholds) -> LaptopOrientation {
+        if thresholds.portrait_left().contains(&self.x) {
             LaptopOrientation::PortraitLeft
-        } else if (5.0..=11.0).contains(&self.x) {
+        } else if thresholds.portrait_right().contains(&self.x) {
             LaptopOrientation::PortraitRight
-        } else if (-11.0..=-7.0).contains(&self.z) {
+        } else if thresholds.tablet().contains(&self.z) {
             // Here we assume that when the screen is close to horizontal facing the sky,
             // the user did put the keyboard behind the screen in "tablet" mode.
             LaptopOrientation::Tablet
-        } else if (7.0..=11.0).contains(&self.y) {
+        } else if thresholds.tent().contains(&self.y) {
             LaptopOrientation::Tent
         } else {
             // safe fallback
@@ -215,41 +352,6 @@ fn normalize(value: f64, scale: f64, offset: f64) -> f64 {
 //
 // --------------------------------------
 
-/// Helper function to reduce duplication of code when calling xrandr.
-fn call_xrandr(orientation: &str, err_msg: &str) -> io::Result<()> {
-    let status = Command::new("xrandr")
-        .args(&["--orientation", orientation])
-        .status()
-        .expect("Couldn't run xrandr, is it properly installed?");
-
-    if status.success() {
-        Ok(())
-    } else {
-        Err(io::Error::new(ErrorKind::Other, err_msg))
-    }
-}
-
-/// Using xrandr, rotate the current output based on the laptop orientation.
-fn rotate_screen_output(orientation: &LaptopOrientation) -> io::Result<()> {
-    match orientation {
-        LaptopOrientation::Normal | LaptopOrientation::Tablet => call_xrandr(
-            "normal",
-            "xrandr couldn't rotate screen in normal orientation",
-        )?,
-        LaptopOrientation::PortraitLeft => {
-            call_xrandr("right", "xrandr couldn't rotate screen right")?
-        }
-        LaptopOrientation::PortraitRight => {
-            call_xrandr("left", "xrandr couldn't rotate screen to the left")?
-        }
-        LaptopOrientation::Tent => {
-            call_xrandr("inverted", "xrandr couldn't rotate screen 180\u{b0}")?
-        }
-    };
-
-    Ok(())
-}
-
 /// Helper that returns elements in `inputs` that match the elements in `to_find`.
 /// Elements in `to_find` must be substrings of elements in `inputs`.
 fn find_inputs<'a>(inputs: &'a [String], to_find: &'a [String]) -> Vec<&'a String> {
@@ -265,50 +367,59 @@ fn find_inputs<'a>(inputs: &'a [String], to_find: &'a [String]) -> Vec<&'a Strin
         .collect::<Vec<&String>>()
 }
 
-/// Using `xinput`, enable or disable the input devices.
-fn toggle_inputs(inputs: &[&String], enable: bool) -> io::Result<()> {
+/// Enable or disable a group of input devices through the backend.
+fn toggle_inputs(backend: &dyn DisplayBackend, inputs: &[&String], enable: bool) -> io::Result<()> {
     for input in inputs {
-        let action = if enable { "enable" } else { "disable" };
-        let failure_msg = format!("xinput couldn't {} {}", action, input);
-        let status = Command::new("xinput")
-            .arg(action)
-            .arg(input) // `keyboard[0]` because I suppose there should be only one integrated keyboard in a laptop
-            .status()
-            .expect("Couldn't run `xinput`, are you sure it's installed properly?");
-        if !status.success() {
-            return Err(io::Error::new(ErrorKind::Other, failure_msg));
-        }
+        backend.toggle_input(input, enable)?;
     }
 
     Ok(())
 }
 
-/// Using `xinput`, enable/disable the laptop keyboard depending on the orientation.
-fn toggle_keyboard(orientation: &LaptopOrientation, inputs: &[String]) -> io::Result<()> {
-    // Singular tense because there should be only one internal keyboard in a laptop, right?
-    let keyboard_to_find = &[String::from("AT Translated Set 2 keyboard")];
-    let keyboard: Vec<&String> = find_inputs(inputs, keyboard_to_find);
+/// Enable/disable the laptop keyboard depending on the orientation.
+fn toggle_keyboard(
+    backend: &dyn DisplayBackend,
+    orientation: LaptopOrientation,
+    inputs: &[String],
+    config: &Config,
+) -> io::Result<()> {
+    let keyboard: Vec<&String> = find_inputs(inputs, &config.devices.keyboard);
 
     if keyboard.is_empty() {
         return Err(io::Error::new(io::ErrorKind::NotFound, "No keyboard found"));
     }
 
     match orientation {
-        LaptopOrientation::Normal => toggle_inputs(&keyboard, true)?,
+        LaptopOrientation::Normal => toggle_inputs(backend, &keyboard, true)?,
         LaptopOrientation::PortraitLeft
         | LaptopOrientation::PortraitRight
         | LaptopOrientation::Tent
-        | LaptopOrientation::Tablet => toggle_inputs(&keyboard, false)?,
+        | LaptopOrientation::Tablet => toggle_inputs(backend, &keyboard, false)?,
     }
 
     Ok(())
 }
 
-/// Using `xinput`, rotate the screen inputs (touchscreen or stylus). Without this when the screen
-/// output is rotated touching part of the screen moves the cursor elsewhere.
-fn rotate_screen_inputs(orientation: &LaptopOrientation, inputs: &[String]) -> io::Result<()> {
-    let screen_inputs_to_find = &[String::from("touchscreen"), String::from("wacom")];
-    let screen_inputs = find_inputs(inputs, screen_inputs_to_find);
+/// Rotate the screen inputs (touchscreen or stylus). Without this when the screen output is
+/// rotated touching part of the screen moves the cursor elsewhere.
+fn rotate_screen_inputs(
+    backend: &dyn DisplayBackend,
+    orientation: LaptopOrientation,
+    inputs: &[String],
+    config: &Config,
+) -> io::Result<()> {
+    let mut screen_inputs: Vec<String> = find_inputs(inputs, &config.devices.touchscreen)
+        .into_iter()
+        .cloned()
+        .collect();
+
+    // Anything advertising a calibration matrix is a touch input worth rotating, even if its
+    // name doesn't match the configured substrings (common on newer libinput-based panels).
+    for input in backend.calibratable_inputs()? {
+        if !screen_inputs.contains(&input) {
+            screen_inputs.push(input);
+        }
+    }
 
     if screen_inputs.is_empty() {
         return Err(io::Error::new(
@@ -317,43 +428,23 @@ fn rotate_screen_inputs(orientation: &LaptopOrientation, inputs: &[String]) -> i
         ));
     }
 
-    let transformation_matrix = match orientation {
-        LaptopOrientation::Normal | LaptopOrientation::Tablet => [1, 0, 0, 0, 1, 0, 0, 0, 1],
-        LaptopOrientation::PortraitLeft => [0, 1, 0, -1, 0, 1, 0, 0, 1],
-        LaptopOrientation::PortraitRight => [0, -1, 1, 1, 0, 0, 0, 0, 1],
-        LaptopOrientation::Tent => [-1, 0, 1, 0, -1, 1, 0, 0, 1],
-    };
-
-    for input in screen_inputs {
-        let mut xinput = Command::new("xinput");
-        let command = xinput
-            .arg("set-prop")
-            .arg(input)
-            .arg("Coordinate Transformation Matrix");
-
-        for number in &transformation_matrix {
-            command.arg(number.to_string());
-        }
-
-        let status = command
-            .status()
-            .expect("Couldn't run `xinput`, are you sure it's installed properly?");
+    let transformation_matrix = display_backend::input_matrix_for(orientation);
 
-        if !status.success() {
-            return Err(io::Error::new(
-                ErrorKind::Other,
-                format!("xinput couldn't rotate '{}'", input),
-            ));
-        }
+    for input in &screen_inputs {
+        backend.set_input_matrix(input, &transformation_matrix)?;
     }
 
     Ok(())
 }
 
-/// Using `xinput`, enable/disable touchpads (physical integrated inputs that move the mouse cursor).
-fn toggle_touchpads(orientation: &LaptopOrientation, inputs: &[String]) -> io::Result<()> {
-    let touchpad_to_find = &[String::from("Touchpad"), String::from("Trackpoint")];
-    let touchpads = find_inputs(inputs, touchpad_to_find);
+/// Enable/disable touchpads (physical integrated inputs that move the mouse cursor).
+fn toggle_touchpads(
+    backend: &dyn DisplayBackend,
+    orientation: LaptopOrientation,
+    inputs: &[String],
+    config: &Config,
+) -> io::Result<()> {
+    let touchpads = find_inputs(inputs, &config.devices.touchpad);
 
     if touchpads.is_empty() {
         return Err(io::Error::new(
@@ -363,11 +454,11 @@ fn toggle_touchpads(orientation: &LaptopOrientation, inputs: &[String]) -> io::R
     }
 
     match orientation {
-        LaptopOrientation::Normal => toggle_inputs(&touchpads, true)?,
+        LaptopOrientation::Normal => toggle_inputs(backend, &touchpads, true)?,
         LaptopOrientation::PortraitLeft
         | LaptopOrientation::PortraitRight
         | LaptopOrientation::Tent
-        | LaptopOrientation::Tablet => toggle_inputs(&touchpads, false)?,
+        | LaptopOrientation::Tablet => toggle_inputs(backend, &touchpads, false)?,
     }
 
     Ok(())
@@ -379,32 +470,155 @@ fn toggle_touchpads(orientation: &LaptopOrientation, inputs: &[String]) -> io::R
 //
 // --------------------------------------
 
-fn main() {
+/// Apply all the side effects (screen rotation, keyboard/touchpad toggling, touch input rotation)
+/// for a given orientation.
+fn apply_orientation(
+    backend: &dyn DisplayBackend,
+    orientation: LaptopOrientation,
+    inputs: &[String],
+    config: &Config,
+) -> io::Result<()> {
+    backend
+        .rotate_output(orientation)
+        .and_then(|()| toggle_keyboard(backend, orientation, inputs, config))
+        .and_then(|()| toggle_touchpads(backend, orientation, inputs, config))
+        .and_then(|()| rotate_screen_inputs(backend, orientation, inputs, config))
+}
+
+/// Read the raw Z axis straight from the sysfs accelerometer, normalized to roughly [-10, 10].
+///
+/// Used to tell `Tablet` apart from `Normal` when the `DBus` backend reports `normal`, since
+/// iio-sensor-proxy has no notion of the screen lying flat.
+fn read_accel_z_raw(paths: &AccelPaths) -> Result<f64, ReadError> {
+    let z = read_value(&paths.z)?;
+    let scale = read_value(&paths.scale)?;
+    let offset = read_value(&paths.offset)?;
+
+    Ok(normalize(z, scale, offset))
+}
+
+/// Resolve `Tablet` out of a DBus-reported `Normal` by checking the raw Z axis, since
+/// iio-sensor-proxy folds both "normal" and "flat" into the same `normal` string.
+fn resolve_tablet(
+    orientation: LaptopOrientation,
+    thresholds: &Thresholds,
+    accel_paths: &AccelPaths,
+) -> LaptopOrientation {
+    if matches!(orientation, LaptopOrientation::Normal) {
+        if let Ok(z) = read_accel_z_raw(accel_paths) {
+            if thresholds.tablet().contains(&z) {
+                return LaptopOrientation::Tablet;
+            }
+        }
+    }
+
+    orientation
+}
+
+/// Poll the raw IIO sysfs files and react to whatever orientation comes out.
+///
+/// This is the fallback used when the `net.hadess.SensorProxy` `DBus` service isn't available.
+fn run_polling_loop(
+    backend: &dyn DisplayBackend,
+    config: &Config,
+    accel_paths: &AccelPaths,
+) -> io::Result<()> {
+    loop {
+        let accel_x = read_value(&accel_paths.x).unwrap();
+        let accel_y = read_value(&accel_paths.y).unwrap();
+        let accel_z = read_value(&accel_paths.z).unwrap();
+        let scale = read_value(&accel_paths.scale).unwrap();
+        let offset = read_value(&accel_paths.offset).unwrap();
+
+        let inputs = backend.list_inputs()?;
+
+        let current_orientation = Accelerometer::new(accel_x, accel_y, accel_z, scale, offset)
+            .which_orientation(&config.thresholds);
+
+        apply_orientation(backend, current_orientation, &inputs, config)?;
+
+        sleep(Duration::from_secs(config.poll_interval_secs));
+    }
+}
+
+/// Claim the accelerometer over `DBus` and react to `PropertiesChanged` signals as they come in,
+/// instead of polling on a timer.
+fn run_event_driven_loop(
+    backend: &dyn DisplayBackend,
+    config: &Config,
+    accel_paths: &AccelPaths,
+) -> io::Result<()> {
+    let sensor = sensor_proxy::SensorProxy::claim().map_err(|err| {
+        io::Error::other(format!("couldn't claim the accelerometer over DBus: {err}"))
+    })?;
+
+    let mut current_orientation = resolve_tablet(
+        sensor
+            .orientation()
+            .map_err(|err| io::Error::other(format!("couldn't read orientation: {err}")))?,
+        &config.thresholds,
+        accel_paths,
+    );
+    apply_orientation(backend, current_orientation, &backend.list_inputs()?, config)?;
+
     loop {
-        let accel_x =
-            read_value(Path::new("/sys/bus/iio/devices/iio:device0/in_accel_x_raw")).unwrap();
-        let accel_y =
-            read_value(Path::new("/sys/bus/iio/devices/iio:device0/in_accel_y_raw")).unwrap();
-        let accel_z =
-            read_value(Path::new("/sys/bus/iio/devices/iio:device0/in_accel_z_raw")).unwrap();
-        let scale =
-            read_value(Path::new("/sys/bus/iio/devices/iio:device0/in_accel_scale")).unwrap();
-        let offset = read_value(Path::new(
-            "/sys/bus/iio/devices/iio:device0/in_accel_offset",
-        ))
-        .unwrap();
-
-        let inputs = list_input_devices().unwrap();
-
-        let current_orientation =
-            Accelerometer::new(accel_x, accel_y, accel_z, scale, offset).which_orientation();
-
-        rotate_screen_output(&current_orientation)
-            .and_then(|_| toggle_keyboard(&current_orientation, &inputs))
-            .and_then(|_| toggle_touchpads(&current_orientation, &inputs))
-            .and_then(|_| rotate_screen_inputs(&current_orientation, &inputs))
-            .unwrap();
-
-        sleep(Duration::from_secs(2));
+        current_orientation = resolve_tablet(
+            sensor.wait_for_change().map_err(|err| {
+                io::Error::other(format!("lost the PropertiesChanged signal stream: {err}"))
+            })?,
+            &config.thresholds,
+            accel_paths,
+        );
+
+        apply_orientation(backend, current_orientation, &backend.list_inputs()?, config)?;
+    }
+}
+
+/// Read the accelerometer a single time and derive the corresponding orientation, for `--oneshot`.
+fn read_current_orientation(config: &Config, accel_paths: &AccelPaths) -> LaptopOrientation {
+    let accel_x = read_value(&accel_paths.x).unwrap();
+    let accel_y = read_value(&accel_paths.y).unwrap();
+    let accel_z = read_value(&accel_paths.z).unwrap();
+    let scale = read_value(&accel_paths.scale).unwrap();
+    let offset = read_value(&accel_paths.offset).unwrap();
+
+    Accelerometer::new(accel_x, accel_y, accel_z, scale, offset).which_orientation(&config.thresholds)
+}
+
+fn main() {
+    let args = cli::Cli::parse();
+    let config = Config::load();
+    let panel_override = args.panel.clone().or_else(|| config.output.panel.clone());
+    let backend = display_backend::detect_backend(panel_override.clone());
+
+    // `--set`/`--next`/`--previous`/`--oneshot` apply one orientation and exit, for use from a
+    // desktop launcher or keybind; with none of them the daemon loop below runs as usual.
+    let one_shot_orientation = if let Some(set) = args.set {
+        Some(set.into())
+    } else if args.next {
+        Some(cli::cycle(1, panel_override.as_deref()))
+    } else if args.previous {
+        Some(cli::cycle(-1, panel_override.as_deref()))
+    } else if args.oneshot {
+        let accel_paths = discover_accelerometer().expect("couldn't find an accelerometer");
+        Some(read_current_orientation(&config, &accel_paths))
+    } else {
+        None
+    };
+
+    if let Some(orientation) = one_shot_orientation {
+        let inputs = backend.list_inputs().unwrap();
+        apply_orientation(backend.as_ref(), orientation, &inputs, &config).unwrap();
+        return;
+    }
+
+    let accel_paths = discover_accelerometer().expect("couldn't find an accelerometer");
+
+    if let Err(err) = run_event_driven_loop(backend.as_ref(), &config, &accel_paths) {
+        eprintln!(
+            "event-driven backend unavailable ({}), falling back to polling every {} seconds",
+            err, config.poll_interval_secs
+        );
+        run_polling_loop(backend.as_ref(), &config, &accel_paths).unwrap();
     }
 }
@@ -0,0 +1,99 @@
+//! Event-driven accelerometer backend talking to the `iio-sensor-proxy` `DBus` service
+//! (`net.hadess.SensorProxy`) instead of polling the IIO sysfs files.
+//!
+//! See <https://gitlab.freedesktop.org/hadess/iio-sensor-proxy> for the service's `DBus` API.
+
+use zbus::blocking::{Connection, Proxy};
+use zbus::Result as ZResult;
+
+use crate::LaptopOrientation;
+
+const DEST: &str = "net.hadess.SensorProxy";
+const PATH: &str = "/net/hadess/SensorProxy";
+const INTERFACE: &str = "net.hadess.SensorProxy";
+
+/// A claimed connection to the `iio-sensor-proxy` accelerometer. The accelerometer is released
+/// when this value is dropped.
+pub struct SensorProxy<'a> {
+    proxy: Proxy<'a>,
+}
+
+impl SensorProxy<'_> {
+    /// Connect to the system bus and claim the accelerometer.
+    pub fn claim() -> ZResult<Self> {
+        let connection = Connection::system()?;
+        let proxy = Proxy::new(&connection, DEST, PATH, INTERFACE)?;
+        proxy.call_method("ClaimAccelerometer", &())?;
+
+        Ok(SensorProxy { proxy })
+    }
+
+    /// Read the current `AccelerometerOrientation` property.
+    ///
+    /// iio-sensor-proxy only ever reports `normal`, `bottom-up`, `left-up` or `right-up`, so
+    /// `Tablet` can't come out of this; callers that care about it should fall back to the raw
+    /// accelerometer Z axis, same as the polling backend does.
+    pub fn orientation(&self) -> ZResult<LaptopOrientation> {
+        let value: String = self.proxy.get_property("AccelerometerOrientation")?;
+        Ok(orientation_from_str(&value))
+    }
+
+    /// Block until `AccelerometerOrientation` changes and return the new value.
+    ///
+    /// This waits on `org.freedesktop.DBus.Properties.PropertiesChanged` so the caller only wakes
+    /// up when there's actually something to do, instead of polling on a timer.
+    ///
+    /// `receive_property_changed` (rather than subscribing to `PropertiesChanged` directly via
+    /// `receive_signal`) is what makes this match the signal's actual header interface,
+    /// `org.freedesktop.DBus.Properties` — `self.proxy`'s own interface,
+    /// `net.hadess.SensorProxy`, only ever appears inside the signal body.
+    pub fn wait_for_change(&self) -> ZResult<LaptopOrientation> {
+        let mut changes = self
+            .proxy
+            .receive_property_changed::<String>("AccelerometerOrientation");
+        let change = changes.next().ok_or(zbus::Error::Unsupported)?;
+
+        Ok(orientation_from_str(&change.get()?))
+    }
+}
+
+impl Drop for SensorProxy<'_> {
+    fn drop(&mut self) {
+        // Best-effort: if the bus is already gone there's nothing useful to do about it.
+        let _ = self.proxy.call_method("ReleaseAccelerometer", &());
+    }
+}
+
+/// Map the orientation strings reported by iio-sensor-proxy onto our own enum.
+///
+/// iio-sensor-proxy names `left-up`/`right-up` after which edge of the device is now up, which is
+/// the opposite of the `PortraitLeft`/`PortraitRight` naming here (named after the direction the
+/// laptop was turned, matching the X11 backend's `xrandr` mapping): turning the laptop left makes
+/// its right edge point up, i.e. `right-up`.
+fn orientation_from_str(value: &str) -> LaptopOrientation {
+    match value {
+        "bottom-up" => LaptopOrientation::Tent,
+        "left-up" => LaptopOrientation::PortraitRight,
+        "right-up" => LaptopOrientation::PortraitLeft,
+        _ => LaptopOrientation::Normal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::orientation_from_str;
+    use crate::LaptopOrientation;
+
+    #[test]
+    fn maps_each_iio_sensor_proxy_string() {
+        assert_eq!(orientation_from_str("normal"), LaptopOrientation::Normal);
+        assert_eq!(orientation_from_str("bottom-up"), LaptopOrientation::Tent);
+        assert_eq!(orientation_from_str("left-up"), LaptopOrientation::PortraitRight);
+        assert_eq!(orientation_from_str("right-up"), LaptopOrientation::PortraitLeft);
+    }
+
+    #[test]
+    fn unknown_string_falls_back_to_normal() {
+        assert_eq!(orientation_from_str("whatever"), LaptopOrientation::Normal);
+    }
+}